@@ -57,25 +57,25 @@ pub fn timing_check() -> bool {
     elapsed > Duration::from_secs(2)
 }
 
-/// Verify the sidecar binary hasn't been tampered with.
+/// Verify the sidecar binary against a detached Ed25519 signature.
 ///
-/// Checks that the binary at the given path matches an expected SHA-256 hash.
-/// The expected hash should be set at build time (e.g. via `include_str!` or
-/// environment variable).
-pub fn verify_binary_integrity(binary_path: &str, expected_hash: &str) -> bool {
+/// Hashes the binary with SHA-256, then verifies `sig_path`'s contents —
+/// `key_id.base64(signature)` — against that digest using the shared license
+/// trust store. This binds the sidecar to the same release key used to sign
+/// licenses, so re-signing a rebuilt sidecar just replaces the `.sig` file,
+/// and a tampered binary fails verification even if an attacker controls the
+/// environment.
+pub fn verify_binary_signature(binary_path: &str, sig_path: &str) -> Result<(), String> {
     use sha2::{Sha256, Digest};
 
-    let bytes = match std::fs::read(binary_path) {
-        Ok(b) => b,
-        Err(_) => return false,
-    };
-
+    let bytes = std::fs::read(binary_path).map_err(|e| format!("Cannot read sidecar binary: {}", e))?;
     let mut hasher = Sha256::new();
     hasher.update(&bytes);
     let digest = hasher.finalize();
-    let hex_hash: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
 
-    hex_hash == expected_hash
+    let sig_blob = std::fs::read_to_string(sig_path)
+        .map_err(|e| format!("Cannot read sidecar signature: {}", e))?;
+    crate::license::verify_detached_signature(&digest, sig_blob.trim())
 }
 
 /// Run all anti-tamper checks. Returns an error message if any fail.
@@ -3,6 +3,7 @@
 mod integrity;
 mod license;
 mod machine_id;
+mod updater;
 
 use std::sync::Mutex;
 use tauri::Emitter;
@@ -22,6 +23,14 @@ fn backend_already_running(port: u16) -> bool {
 /// Holds the sidecar child process so we can kill it on app exit.
 struct SidecarChild(Mutex<Option<CommandChild>>);
 
+/// Holds the most recently computed license status so `get_license_status`
+/// can answer instantly without touching the network.
+struct LicenseManagerState(Mutex<license::LicenseStatus>);
+
+/// How often the background license manager re-validates the license and
+/// re-checks revocation while the app is running.
+const LICENSE_RECHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
 // ── Tauri commands for license operations ──────────────────────────────
 
 /// Return the machine hardware fingerprint (SHA-256 hex of HW identifiers).
@@ -65,6 +74,64 @@ fn get_license_path() -> String {
     license::license_file_path().to_string_lossy().to_string()
 }
 
+/// Read the cached license status without blocking on network I/O.
+///
+/// The background license manager keeps this up to date on
+/// [`LICENSE_RECHECK_INTERVAL`], so the frontend can poll this cheaply. Unlike
+/// `validate_license`, which only re-verifies the stored file, this reflects
+/// the manager's last full check, including clock drift and revocation.
+#[tauri::command]
+fn get_license_status(app: tauri::AppHandle) -> license::LicenseStatus {
+    let state = app.state::<LicenseManagerState>();
+    state
+        .0
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_else(|_| license::validate_stored_license())
+}
+
+// ── Tauri commands for the updater ─────────────────────────────────────
+
+/// Check the update server for a newer version on the user's chosen channel.
+#[tauri::command]
+async fn check_for_updates() -> updater::UpdateCheckResult {
+    updater::check_for_updates().await
+}
+
+/// Download and install the update described by `manifest`.
+#[tauri::command]
+async fn download_and_install_update(
+    manifest: updater::UpdateManifest,
+    app: tauri::AppHandle,
+) -> updater::InstallResult {
+    updater::download_and_install(&manifest, &app).await
+}
+
+/// Read and validate the manifest embedded in an offline update package,
+/// without installing it.
+#[tauri::command]
+fn read_offline_update_package(path: String) -> Result<updater::OfflinePackageMeta, String> {
+    updater::read_offline_package(&path)
+}
+
+/// Install an offline update package picked by the user.
+#[tauri::command]
+fn install_offline_update_package(path: String) -> updater::InstallResult {
+    updater::install_offline_package(&path)
+}
+
+/// Get the user's persisted release channel ("stable", "beta", "nightly").
+#[tauri::command]
+fn get_update_channel() -> String {
+    updater::get_channel()
+}
+
+/// Persist the user's chosen release channel.
+#[tauri::command]
+fn set_update_channel(channel: String) -> Result<(), String> {
+    updater::set_channel(&channel)
+}
+
 // ── Sidecar launcher (gated behind license check) ─────────────────────
 
 /// Attempt to start the sidecar if a valid license is present.
@@ -104,17 +171,29 @@ async fn start_backend(app: tauri::AppHandle) -> Result<String, String> {
         .sidecar("doc-anonymizer-sidecar")
         .map_err(|e| format!("Failed to create sidecar command: {}", e))?;
 
-    // H12: Verify sidecar binary integrity before spawning (if hash is available)
-    // In production, set SIDECAR_HASH at build time.
-    if let Ok(expected_hash) = std::env::var("SIDECAR_EXPECTED_HASH") {
-        let sidecar_path = app
-            .path()
-            .resource_dir()
-            .map(|d| d.join("binaries").join("doc-anonymizer-sidecar"))
-            .unwrap_or_default();
-        let path_str = sidecar_path.to_string_lossy();
-        if !path_str.is_empty() && !integrity::verify_binary_integrity(&path_str, &expected_hash) {
-            return Err("Sidecar binary integrity check failed — possible tampering".to_string());
+    // H12: Verify the sidecar binary against its detached signature before
+    // spawning — binds it to the same release key used to sign licenses.
+    let sidecar_path = app
+        .path()
+        .resource_dir()
+        .map(|d| d.join("binaries").join("doc-anonymizer-sidecar"))
+        .unwrap_or_default();
+    if !sidecar_path.as_os_str().is_empty() {
+        let sig_path = sidecar_path.with_extension("sig");
+        if let Err(e) = integrity::verify_binary_signature(
+            &sidecar_path.to_string_lossy(),
+            &sig_path.to_string_lossy(),
+        ) {
+            // Debug builds commonly run against a locally built sidecar with
+            // no `.sig` next to it; only enforce this in release builds, same
+            // as the other integrity checks in `setup()`.
+            if !cfg!(debug_assertions) {
+                return Err(format!(
+                    "Sidecar binary signature check failed — possible tampering: {}",
+                    e
+                ));
+            }
+            eprintln!("[SECURITY] Sidecar signature check skipped in debug build: {}", e);
         }
     }
 
@@ -169,11 +248,91 @@ async fn start_backend(app: tauri::AppHandle) -> Result<String, String> {
     }
 }
 
+/// Run the full (network-touching) license check: stored-license validity,
+/// clock-drift, and server-side revocation. Shared by the immediate check
+/// `setup` spawns on startup, and by [`run_license_manager`]'s periodic
+/// re-check.
+async fn run_full_license_check() -> license::LicenseStatus {
+    let fingerprint = machine_id::get_machine_fingerprint();
+    let mut status = license::validate_for_machine(&fingerprint);
+
+    if status.valid {
+        if let Err(e) = license::check_clock_drift().await {
+            status.valid = false;
+            status.error = Some(e);
+        }
+    }
+    if status.valid {
+        let licensing_url = std::env::var("LICENSING_URL").unwrap_or_else(|_| {
+            "https://licensing-server-455859748614.us-east4.run.app".to_string()
+        });
+        if let Err(e) = license::check_revocation(&licensing_url, &fingerprint).await {
+            status.valid = false;
+            status.error = Some(e);
+        }
+    }
+
+    status
+}
+
+/// Store `status` into [`LicenseManagerState`] and emit `license-status`. On
+/// a transition from valid to invalid, also kills the stored [`SidecarChild`]
+/// so a license that expires or is revoked mid-session doesn't leave it
+/// running indefinitely.
+fn apply_license_status(app: &tauri::AppHandle, status: license::LicenseStatus) {
+    let became_invalid = {
+        let state = app.state::<LicenseManagerState>();
+        match state.0.lock() {
+            Ok(mut guard) => {
+                let was_valid = guard.valid;
+                *guard = status.clone();
+                was_valid && !status.valid
+            }
+            Err(_) => false,
+        }
+    };
+
+    if became_invalid {
+        let reason = status.error.as_deref().unwrap_or("license is no longer valid");
+        eprintln!("[license] killing sidecar: {}", reason);
+        let sidecar_state = app.state::<SidecarChild>();
+        if let Ok(mut guard) = sidecar_state.0.lock() {
+            if let Some(child) = guard.take() {
+                let _ = child.kill();
+            }
+        }
+    }
+
+    let _ = app.emit("license-status", &status);
+}
+
+/// Background task that periodically re-runs [`run_full_license_check`] and
+/// applies the result via [`apply_license_status`], for the lifetime of the
+/// app. The caller is expected to have already run and applied one check
+/// immediately on startup — this only handles the steady-state re-checks.
+async fn run_license_manager(app: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(LICENSE_RECHECK_INTERVAL);
+    interval.tick().await; // first tick is immediate; the caller already ran the initial check
+    loop {
+        interval.tick().await;
+        let status = run_full_license_check().await;
+        apply_license_status(&app, status);
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Cheap offline-only placeholder so `LicenseManagerState` has something
+    // to manage before the builder exists to run the real, network-touching
+    // check. `setup` starts that check in a background task and overwrites
+    // this with its result once it finishes; until then, commands see this
+    // offline-only status.
+    let initial_status = license::validate_stored_license();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .manage(SidecarChild(Mutex::new(None)))
+        .manage(LicenseManagerState(Mutex::new(initial_status)))
         .invoke_handler(tauri::generate_handler![
             get_machine_id,
             get_machine_name,
@@ -181,7 +340,14 @@ pub fn run() {
             store_license,
             clear_license,
             get_license_path,
+            get_license_status,
             start_backend,
+            check_for_updates,
+            download_and_install_update,
+            read_offline_update_package,
+            install_offline_update_package,
+            get_update_channel,
+            set_update_channel,
         ])
         .setup(|app| {
             // Run integrity checks (anti-debug, timing)
@@ -193,13 +359,32 @@ pub fn run() {
                 }
             }
 
-            // Emit initial license status so frontend knows whether to show
-            // the auth screen or the main app.
-            let status = license::validate_stored_license();
+            // Remove a `.old` binary left behind by a previous self-update swap.
+            updater::cleanup_old_binary();
+
+            // Consult the cached offline revocation list before anything else,
+            // so a machine revoked while offline stays locked out on startup.
+            let fingerprint = machine_id::get_machine_fingerprint();
+            if let Err(e) = license::check_cached_revocation(&fingerprint) {
+                eprintln!("[SECURITY] {}", e);
+            }
+
+            // Run the full license check once, immediately, in a background
+            // task, so `LicenseManagerState` (and therefore
+            // `get_license_status`) is replaced as soon as that check finishes
+            // rather than reporting the pre-setup offline-only status for up
+            // to `LICENSE_RECHECK_INTERVAL`. This also emits the initial
+            // `license-status` event the frontend uses to decide whether to
+            // show the auth screen or the main app, then starts the
+            // long-lived manager that keeps re-checking for the app's
+            // lifetime.
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                let _ = app_handle.emit("license-status", &status);
+                let status = run_full_license_check().await;
+                apply_license_status(&app_handle, status);
+                run_license_manager(app_handle).await;
             });
+
             Ok(())
         })
         .build(tauri::generate_context!())
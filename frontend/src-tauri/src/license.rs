@@ -12,15 +12,25 @@ use ed25519_dalek::{Signature, VerifyingKey, Verifier};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-// ── Ed25519 public key (set during build / release) ────────────────────
+// ── Ed25519 root-of-trust store (set during build / release) ───────────
 // Generated 2026-02-11 via src-licensing/generate_keys.py.
-// Must match the private key in the licensing server's .env.
-const ED25519_PUBLIC_KEY_B64: &str = "B4EIWiBILG2lIl4tq4KeQsm/Vh2Z3q5YUpsl2yxH1q4=";
-
-// C3: Compile-time check — fail release builds if the placeholder key is still present
+// Must match the private key(s) in the licensing server's .env.
+//
+// Ordered oldest-first; append an entry when the signing key is rotated so
+// shipped builds keep verifying both old and new blobs during the overlap
+// window. Each license blob carries a leading `key_id` identifying which
+// entry signed it.
+const TRUST_STORE: &[(u8, &str)] = &[(1, "B4EIWiBILG2lIl4tq4KeQsm/Vh2Z3q5YUpsl2yxH1q4=")];
+
+/// Floor below which a `key_id` is refused even though its bytes are still
+/// present in `TRUST_STORE` above. Bump this in a release to retire a
+/// known-compromised key without removing the old entry (older blobs signed
+/// with it simply stop verifying).
+const MIN_KEY_ID: u8 = 1;
+
+// C3: Compile-time check — fail release builds if a placeholder key is still present
 #[cfg(not(debug_assertions))]
 const _: () = {
-    const KEY: &[u8] = ED25519_PUBLIC_KEY_B64.as_bytes();
     // Check if it's the all-A placeholder (base64 of all zeros)
     // All-A pattern: 43 'A' chars + '='
     const fn is_all_a(key: &[u8]) -> bool {
@@ -33,21 +43,61 @@ const _: () = {
         }
         true
     }
-    assert!(
-        !is_all_a(KEY),
-        "CRITICAL: ED25519_PUBLIC_KEY_B64 is still the placeholder. Set the real public key before building a release."
-    );
+    let mut i = 0;
+    while i < TRUST_STORE.len() {
+        assert!(
+            !is_all_a(TRUST_STORE[i].1.as_bytes()),
+            "CRITICAL: TRUST_STORE still contains the placeholder key. Set the real public key(s) before building a release."
+        );
+        i += 1;
+    }
 };
 
+/// `issued`/`expires` timestamp — either a signed Unix-epoch second count
+/// (`v >= 2` payloads) or an RFC3339 string (`v == 1`, kept for blobs already
+/// on disk). Epoch timestamps remove the timezone/offset parsing this module
+/// used to need and make expiry a plain integer comparison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LicenseTimestamp {
+    Epoch(i64),
+    Rfc3339(String),
+}
+
+impl LicenseTimestamp {
+    /// Resolve to a Unix-epoch second count, parsing the RFC3339 form if needed.
+    pub fn as_epoch(&self) -> Result<i64, String> {
+        match self {
+            LicenseTimestamp::Epoch(secs) => Ok(*secs),
+            LicenseTimestamp::Rfc3339(s) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc).timestamp())
+                .map_err(|e| format!("Bad timestamp {}: {}", s, e)),
+        }
+    }
+}
+
+impl std::fmt::Display for LicenseTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicenseTimestamp::Epoch(secs) => write!(f, "{}", secs),
+            LicenseTimestamp::Rfc3339(s) => write!(f, "{}", s),
+        }
+    }
+}
+
 /// Parsed license payload — matches the JSON the server signs.
+///
+/// `v == 1` blobs carry RFC3339 `issued`/`expires` strings; `v == 2` blobs
+/// (the current format minted by the licensing server) carry Unix-epoch
+/// integers instead. Both forms deserialize through [`LicenseTimestamp`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LicensePayload {
     pub email: String,
     pub plan: String,
     pub seats: u32,
     pub machine_id: String,
-    pub issued: String,
-    pub expires: String,
+    pub issued: LicenseTimestamp,
+    pub expires: LicenseTimestamp,
     #[serde(default = "default_version")]
     pub v: u32,
 }
@@ -58,16 +108,21 @@ fn default_version() -> u32 {
 
 impl LicensePayload {
     /// Check whether the license has expired.
+    ///
+    /// A timestamp that fails to parse is treated as expired, so a malformed
+    /// `expires` fails closed rather than open.
     pub fn is_expired(&self) -> bool {
-        DateTime::parse_from_rfc3339(&self.expires)
-            .map(|exp| exp.with_timezone(&Utc) < Utc::now())
+        self.expires
+            .as_epoch()
+            .map(|exp| exp < Utc::now().timestamp())
             .unwrap_or(true)
     }
 
     /// Days until expiry (negative = already expired).
     pub fn days_remaining(&self) -> i64 {
-        DateTime::parse_from_rfc3339(&self.expires)
-            .map(|exp| (exp.with_timezone(&Utc) - Utc::now()).num_days())
+        self.expires
+            .as_epoch()
+            .map(|exp| (exp - Utc::now().timestamp()).div_euclid(86_400))
             .unwrap_or(-1)
     }
 }
@@ -81,40 +136,185 @@ pub struct LicenseStatus {
     pub days_remaining: Option<i64>,
 }
 
+// ── Delegated signing (license chains) ─────────────────────────────────
+
+/// Intermediate signing key descriptor embedded in a delegated license chain.
+///
+/// The root key (the [`TRUST_STORE`] entry named by the blob's `key_id`) signs this
+/// descriptor, bounding the intermediate key to a `[valid_from, valid_to]`
+/// window so a compromised online signing key can only mint licenses inside
+/// that window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IntermediateKey {
+    /// Base64-encoded Ed25519 public key of the intermediate signer.
+    key: String,
+    /// Unix-epoch seconds — earliest `issued`/`expires` this key may sign for.
+    valid_from: i64,
+    /// Unix-epoch seconds — latest `issued`/`expires` this key may sign for.
+    valid_to: i64,
+}
+
+fn decode_verifying_key(b64: &str) -> Result<VerifyingKey, String> {
+    let bytes = B64.decode(b64).map_err(|e| format!("Bad public key base64: {}", e))?;
+    let array: [u8; 32] = bytes.try_into().map_err(|_| "Public key must be 32 bytes".to_string())?;
+    VerifyingKey::from_bytes(&array).map_err(|e| format!("Invalid public key: {}", e))
+}
+
+fn decode_signature(b64: &str) -> Result<Signature, String> {
+    let bytes = B64.decode(b64).map_err(|e| format!("Bad signature base64: {}", e))?;
+    let array: [u8; 64] = bytes.try_into().map_err(|_| "Signature must be 64 bytes".to_string())?;
+    Ok(Signature::from_bytes(&array))
+}
+
+/// Verify a detached signature against the embedded trust store.
+///
+/// `sig_blob` is `key_id.base64(signature)`. Used by
+/// [`crate::integrity::verify_binary_signature`] to bind the sidecar binary
+/// to the same release keys used to sign licenses.
+pub fn verify_detached_signature(message: &[u8], sig_blob: &str) -> Result<(), String> {
+    let parts: Vec<&str> = sig_blob.split('.').collect();
+    let [key_id_s, sig_b64] = parts.as_slice() else {
+        return Err("Invalid signature format".to_string());
+    };
+    let key_id: u8 = key_id_s
+        .parse()
+        .map_err(|_| "Invalid signature format: missing key id".to_string())?;
+    let signature = decode_signature(sig_b64)?;
+    root_verifying_key(key_id)?
+        .verify(message, &signature)
+        .map_err(|_| "Signature verification failed".to_string())
+}
+
+/// Look up the root verifying key for `key_id` in [`TRUST_STORE`], rejecting
+/// ids below [`MIN_KEY_ID`] even if the key bytes are still present.
+fn root_verifying_key(key_id: u8) -> Result<VerifyingKey, String> {
+    root_verifying_key_from(TRUST_STORE, MIN_KEY_ID, key_id)
+}
+
+/// Look up `key_id` in an arbitrary `store`/`min_key_id` pair — factored out
+/// of [`root_verifying_key`] so tests can exercise the retirement-floor and
+/// lookup logic against a throwaway store instead of the real compiled-in
+/// [`TRUST_STORE`] (whose private key isn't available to sign test fixtures).
+fn root_verifying_key_from(
+    store: &[(u8, &str)],
+    min_key_id: u8,
+    key_id: u8,
+) -> Result<VerifyingKey, String> {
+    if key_id < min_key_id {
+        return Err(format!("License signing key {} has been retired", key_id));
+    }
+    let (_, key_b64) = store
+        .iter()
+        .find(|(id, _)| *id == key_id)
+        .ok_or_else(|| format!("Unknown license signing key id {}", key_id))?;
+    decode_verifying_key(key_b64)
+}
+
 // ── Core verification ──────────────────────────────────────────────────
 
-/// Verify a license blob string: `base64(json_payload).base64(signature)`.
+/// Verify a license blob.
+///
+/// Current blobs start with a decimal `key_id` segment identifying which
+/// [`TRUST_STORE`] entry signed it (at the root level), followed by one of
+/// two shapes:
+/// - Single-segment: `key_id.base64(json_payload).base64(root_signature)`,
+///   signed directly by the root key.
+/// - Delegated chain (5 segments):
+///   `key_id.base64(root_sig_of_intermediate).base64(intermediate_json).base64(intermediate_sig_of_payload).base64(json_payload)`,
+///   where the root key signs an [`IntermediateKey`] descriptor that in turn
+///   signs the payload. This lets the server rotate an online signing key
+///   without reissuing every license from the offline root.
 ///
-/// Returns `Ok(LicensePayload)` if the signature is valid and not expired.
+/// Blobs written before the key-id prefix existed are exactly two segments —
+/// `base64(json_payload).base64(signature)`, no key id — and are still
+/// accepted, verified against the lowest-numbered [`TRUST_STORE`] entry (the
+/// only key that could have signed them), so installs that activated before
+/// this format existed don't get locked out.
+///
+/// Returns `Ok(LicensePayload)` if the chain verifies and the payload is not expired.
 pub fn verify_license_blob(blob: &str) -> Result<LicensePayload, String> {
-    let parts: Vec<&str> = blob.splitn(2, '.').collect();
-    if parts.len() != 2 {
+    verify_license_blob_from(blob, TRUST_STORE, MIN_KEY_ID)
+}
+
+/// Core of [`verify_license_blob`], parameterized over the trust store so
+/// tests can verify chains signed with a throwaway keypair instead of the
+/// real compiled-in [`TRUST_STORE`] (whose private key isn't available to
+/// sign test fixtures).
+fn verify_license_blob_from(
+    blob: &str,
+    store: &[(u8, &str)],
+    min_key_id: u8,
+) -> Result<LicensePayload, String> {
+    let parts: Vec<&str> = blob.split('.').collect();
+    if parts.is_empty() {
         return Err("Invalid license format".to_string());
     }
 
-    let payload_bytes = B64.decode(parts[0]).map_err(|e| format!("Bad payload base64: {}", e))?;
-    let sig_bytes = B64.decode(parts[1]).map_err(|e| format!("Bad signature base64: {}", e))?;
-
-    // Decode public key
-    let pub_key_bytes = B64
-        .decode(ED25519_PUBLIC_KEY_B64)
-        .map_err(|e| format!("Bad public key: {}", e))?;
-    let pub_key_array: [u8; 32] = pub_key_bytes
-        .try_into()
-        .map_err(|_| "Public key must be 32 bytes".to_string())?;
-    let verifying_key =
-        VerifyingKey::from_bytes(&pub_key_array).map_err(|e| format!("Invalid public key: {}", e))?;
-
-    // Decode signature
-    let sig_array: [u8; 64] = sig_bytes
-        .try_into()
-        .map_err(|_| "Signature must be 64 bytes".to_string())?;
-    let signature = Signature::from_bytes(&sig_array);
-
-    // Verify
-    verifying_key
-        .verify(&payload_bytes, &signature)
-        .map_err(|_| "Signature verification failed — license is invalid or tampered".to_string())?;
+    let payload_bytes = if parts.len() == 2 {
+        // Legacy (pre key-id) blob: `base64(payload).base64(sig)`, always
+        // signed by the original, lowest-numbered trust-store key.
+        let legacy_key_id = store
+            .iter()
+            .map(|(id, _)| *id)
+            .min()
+            .ok_or_else(|| "No trust store keys configured".to_string())?;
+        let payload_bytes =
+            B64.decode(parts[0]).map_err(|e| format!("Bad payload base64: {}", e))?;
+        let signature = decode_signature(parts[1])?;
+        root_verifying_key_from(store, min_key_id, legacy_key_id)?
+            .verify(&payload_bytes, &signature)
+            .map_err(|_| "Signature verification failed — license is invalid or tampered".to_string())?;
+        payload_bytes
+    } else {
+        let key_id: u8 = parts[0]
+            .parse()
+            .map_err(|_| "Invalid license format: missing key id".to_string())?;
+
+        match &parts[1..] {
+            [payload_b64, sig_b64] => {
+                let payload_bytes =
+                    B64.decode(payload_b64).map_err(|e| format!("Bad payload base64: {}", e))?;
+                let signature = decode_signature(sig_b64)?;
+                root_verifying_key_from(store, min_key_id, key_id)?
+                    .verify(&payload_bytes, &signature)
+                    .map_err(|_| "Signature verification failed — license is invalid or tampered".to_string())?;
+                payload_bytes
+            }
+            [root_sig_b64, intermediate_b64, intermediate_sig_b64, payload_b64] => {
+                let intermediate_bytes = B64
+                    .decode(intermediate_b64)
+                    .map_err(|e| format!("Bad intermediate base64: {}", e))?;
+                let root_sig = decode_signature(root_sig_b64)?;
+                root_verifying_key_from(store, min_key_id, key_id)?
+                    .verify(&intermediate_bytes, &root_sig)
+                    .map_err(|_| "Intermediate key signature verification failed".to_string())?;
+
+                let intermediate: IntermediateKey = serde_json::from_slice(&intermediate_bytes)
+                    .map_err(|e| format!("Bad intermediate JSON: {}", e))?;
+                let intermediate_key = decode_verifying_key(&intermediate.key)?;
+
+                let payload_bytes =
+                    B64.decode(payload_b64).map_err(|e| format!("Bad payload base64: {}", e))?;
+                let payload_sig = decode_signature(intermediate_sig_b64)?;
+                intermediate_key
+                    .verify(&payload_bytes, &payload_sig)
+                    .map_err(|_| "Signature verification failed — license is invalid or tampered".to_string())?;
+
+                // Parse just enough of the payload early to enforce the nesting
+                // invariant before returning it below.
+                let payload: LicensePayload = serde_json::from_slice(&payload_bytes)
+                    .map_err(|e| format!("Bad payload JSON: {}", e))?;
+                let issued = payload.issued.as_epoch()?;
+                let expires = payload.expires.as_epoch()?;
+                if issued < intermediate.valid_from || expires > intermediate.valid_to {
+                    return Err("license validity exceeds delegated signing window".to_string());
+                }
+
+                payload_bytes
+            }
+            _ => return Err("Invalid license format".to_string()),
+        }
+    };
 
     // Parse payload
     let payload: LicensePayload = serde_json::from_slice(&payload_bytes)
@@ -278,13 +478,101 @@ pub async fn check_clock_drift() -> Result<(), String> {
     Ok(())
 }
 
-// ── S1: Server-side revocation check ───────────────────────────────────
+// ── S1: Signed, cacheable offline revocation list ───────────────────────
 
-/// Check with the licensing server whether this machine's license has been
-/// revoked (e.g. subscription cancelled, machine deactivated from dashboard).
+/// Signed revocation list published by the licensing server.
 ///
-/// Fails *open* — if the network is unreachable the app continues with the
-/// local blob. Only blocks when the server explicitly says `revoked: true`.
+/// Blob format is `key_id.base64(json).base64(signature)`, verified against
+/// the same [`TRUST_STORE`] used for licenses, so a cached copy on disk is
+/// tamper-evident even without network access.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationList {
+    pub revoked_machine_ids: Vec<String>,
+    /// Unix-epoch seconds after which this list must no longer be trusted —
+    /// forces the server to keep publishing fresh lists rather than letting
+    /// one old list revoke forever.
+    pub not_valid_after: i64,
+    /// Monotonically increasing sequence number. A list with a lower `seq`
+    /// than the freshest one already cached is rejected, so an attacker who
+    /// can write to disk can't roll back to an older, more permissive list.
+    pub seq: u64,
+}
+
+/// Verify a signed revocation list blob and parse it.
+pub fn verify_revocation_list_blob(blob: &str) -> Result<RevocationList, String> {
+    verify_revocation_list_blob_from(blob, TRUST_STORE, MIN_KEY_ID)
+}
+
+/// Core of [`verify_revocation_list_blob`], parameterized over the trust
+/// store for the same reason as [`verify_license_blob_from`].
+fn verify_revocation_list_blob_from(
+    blob: &str,
+    store: &[(u8, &str)],
+    min_key_id: u8,
+) -> Result<RevocationList, String> {
+    let parts: Vec<&str> = blob.split('.').collect();
+    let [key_id_s, payload_b64, sig_b64] = parts.as_slice() else {
+        return Err("Invalid revocation list format".to_string());
+    };
+    let key_id: u8 = key_id_s
+        .parse()
+        .map_err(|_| "Invalid revocation list format: missing key id".to_string())?;
+    let payload_bytes =
+        B64.decode(payload_b64).map_err(|e| format!("Bad payload base64: {}", e))?;
+    let signature = decode_signature(sig_b64)?;
+    root_verifying_key_from(store, min_key_id, key_id)?
+        .verify(&payload_bytes, &signature)
+        .map_err(|_| "Revocation list signature verification failed".to_string())?;
+    serde_json::from_slice(&payload_bytes).map_err(|e| format!("Bad revocation list JSON: {}", e))
+}
+
+/// Path where the verified revocation list is cached, next to `license.key`.
+pub fn revocation_list_path() -> PathBuf {
+    let data_dir = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    data_dir.join("promptshield").join("revocation.list")
+}
+
+fn read_cached_revocation_list() -> Option<RevocationList> {
+    let blob = std::fs::read_to_string(revocation_list_path()).ok()?;
+    verify_revocation_list_blob(blob.trim()).ok()
+}
+
+fn write_cached_revocation_list(blob: &str) -> Result<(), String> {
+    let path = revocation_list_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Cannot create revocation list dir: {}", e))?;
+    }
+    std::fs::write(&path, blob).map_err(|e| format!("Cannot write revocation list: {}", e))
+}
+
+/// Consult the cached revocation list offline: if `machine_fingerprint` is
+/// listed and the list hasn't passed its `not_valid_after`, refuse to start
+/// and delete the local license. Called both as the network fallback below
+/// and on startup, so revocation is effective even while fully offline.
+pub fn check_cached_revocation(machine_fingerprint: &str) -> Result<(), String> {
+    let Some(list) = read_cached_revocation_list() else {
+        return Ok(()); // no cached list yet — nothing to enforce
+    };
+    if Utc::now().timestamp() > list.not_valid_after {
+        return Ok(()); // stale cached list — treat as absent rather than trust old data
+    }
+    if list.revoked_machine_ids.iter().any(|id| id == machine_fingerprint) {
+        let _ = delete_license_file();
+        return Err(
+            "Your license has been revoked. Please sign in again or contact support.".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Fetch the latest signed revocation list, verify and cache it, then check
+/// whether this machine's license has been revoked.
+///
+/// Fails *open* on network/parse errors by falling back to the last verified
+/// list already cached on disk — so a user who stays offline is still
+/// covered by whatever revocation list they last saw, instead of never
+/// being revocable at all.
 pub async fn check_revocation(
     licensing_url: &str,
     machine_fingerprint: &str,
@@ -294,33 +582,201 @@ pub async fn check_revocation(
         .build()
         .map_err(|e| format!("HTTP client error: {e}"))?;
 
-    let url = format!(
-        "{}/license/check-revocation?machine_fingerprint={}",
-        licensing_url, machine_fingerprint,
-    );
+    let url = format!("{}/license/revocation-list", licensing_url);
 
     let resp = match client.get(&url).send().await {
         Ok(r) if r.status().is_success() => r,
-        _ => return Ok(()), // network unreachable — fail open
+        _ => return check_cached_revocation(machine_fingerprint), // network unreachable
     };
 
-    #[derive(serde::Deserialize)]
-    struct RevocationResp {
-        revoked: bool,
-    }
-
-    let body: RevocationResp = match resp.json().await {
+    let blob = match resp.text().await {
         Ok(b) => b,
-        Err(_) => return Ok(()), // malformed response — fail open
+        Err(_) => return check_cached_revocation(machine_fingerprint), // malformed response
     };
 
-    if body.revoked {
-        // Delete the local license file so the user must re-authenticate
-        let _ = delete_license_file();
-        return Err(
-            "Your license has been revoked. Please sign in again or contact support.".to_string(),
+    let list = match verify_revocation_list_blob(blob.trim()) {
+        Ok(l) => l,
+        Err(_) => return check_cached_revocation(machine_fingerprint), // unsigned/tampered
+    };
+
+    if !should_accept_revocation_list(&list, read_cached_revocation_list().as_ref()) {
+        // Older than what we've already seen — an attacker could be
+        // replaying a stale list to un-revoke a machine. Keep the cached one.
+        return check_cached_revocation(machine_fingerprint);
+    }
+    let _ = write_cached_revocation_list(blob.trim());
+
+    check_cached_revocation(machine_fingerprint)
+}
+
+/// Whether a freshly fetched revocation list should replace `cached`: true
+/// unless it's strictly older by `seq`, so a replayed stale list can't
+/// un-revoke a machine.
+fn should_accept_revocation_list(new: &RevocationList, cached: Option<&RevocationList>) -> bool {
+    match cached {
+        Some(cached) => new.seq >= cached.seq,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    /// Fixed seed so tests are deterministic — these keys only ever sign
+    /// fixtures in this module and are never the real release key.
+    fn test_root_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    #[test]
+    fn v1_rfc3339_blob_still_verifies() {
+        let root = test_root_key();
+        let root_b64 = B64.encode(root.verifying_key().to_bytes());
+        let store: &[(u8, &str)] = &[(1, &root_b64)];
+
+        let payload = LicensePayload {
+            email: "user@example.com".to_string(),
+            plan: "pro".to_string(),
+            seats: 1,
+            machine_id: "machine-abc".to_string(),
+            issued: LicenseTimestamp::Rfc3339("2020-01-01T00:00:00Z".to_string()),
+            expires: LicenseTimestamp::Rfc3339("2999-01-01T00:00:00Z".to_string()),
+            v: 1,
+        };
+        let payload_bytes = serde_json::to_vec(&payload).unwrap();
+        let sig = root.sign(&payload_bytes);
+        let blob = format!(
+            "1.{}.{}",
+            B64.encode(&payload_bytes),
+            B64.encode(sig.to_bytes())
+        );
+
+        let verified = verify_license_blob_from(&blob, store, 1).unwrap();
+        assert_eq!(verified.email, "user@example.com");
+    }
+
+    #[test]
+    fn legacy_two_part_blob_without_key_id_still_verifies() {
+        let root = test_root_key();
+        let root_b64 = B64.encode(root.verifying_key().to_bytes());
+        let store: &[(u8, &str)] = &[(1, &root_b64)];
+
+        let payload = LicensePayload {
+            email: "legacy@example.com".to_string(),
+            plan: "pro".to_string(),
+            seats: 1,
+            machine_id: "machine-abc".to_string(),
+            issued: LicenseTimestamp::Rfc3339("2019-01-01T00:00:00Z".to_string()),
+            expires: LicenseTimestamp::Rfc3339("2999-01-01T00:00:00Z".to_string()),
+            v: 1,
+        };
+        let payload_bytes = serde_json::to_vec(&payload).unwrap();
+        let sig = root.sign(&payload_bytes);
+        // No key-id segment — the format every license on disk used before
+        // key rotation was introduced.
+        let blob = format!("{}.{}", B64.encode(&payload_bytes), B64.encode(sig.to_bytes()));
+
+        let verified = verify_license_blob_from(&blob, store, 1).unwrap();
+        assert_eq!(verified.email, "legacy@example.com");
+    }
+
+    #[test]
+    fn legacy_blob_is_refused_once_original_key_is_retired() {
+        let root = test_root_key();
+        let root_b64 = B64.encode(root.verifying_key().to_bytes());
+        let store: &[(u8, &str)] = &[(1, &root_b64)];
+
+        let payload = LicensePayload {
+            email: "legacy@example.com".to_string(),
+            plan: "pro".to_string(),
+            seats: 1,
+            machine_id: "machine-abc".to_string(),
+            issued: LicenseTimestamp::Rfc3339("2019-01-01T00:00:00Z".to_string()),
+            expires: LicenseTimestamp::Rfc3339("2999-01-01T00:00:00Z".to_string()),
+            v: 1,
+        };
+        let payload_bytes = serde_json::to_vec(&payload).unwrap();
+        let sig = root.sign(&payload_bytes);
+        let blob = format!("{}.{}", B64.encode(&payload_bytes), B64.encode(sig.to_bytes()));
+
+        // `min_key_id` of 2 retires key 1, so the legacy blob it implicitly
+        // signed should stop verifying just like a keyed blob would.
+        assert!(verify_license_blob_from(&blob, store, 2).is_err());
+    }
+
+    #[test]
+    fn delegated_chain_exceeding_intermediate_window_is_rejected() {
+        let root = test_root_key();
+        let root_b64 = B64.encode(root.verifying_key().to_bytes());
+        let store: &[(u8, &str)] = &[(1, &root_b64)];
+        let intermediate_signer = SigningKey::from_bytes(&[9u8; 32]);
+
+        let intermediate = IntermediateKey {
+            key: B64.encode(intermediate_signer.verifying_key().to_bytes()),
+            valid_from: 1_700_000_000,
+            valid_to: 1_700_003_600,
+        };
+        let intermediate_bytes = serde_json::to_vec(&intermediate).unwrap();
+        let root_sig = root.sign(&intermediate_bytes);
+
+        // `expires` falls well after the intermediate's `valid_to` window.
+        let payload = LicensePayload {
+            email: "user@example.com".to_string(),
+            plan: "pro".to_string(),
+            seats: 1,
+            machine_id: "machine-abc".to_string(),
+            issued: LicenseTimestamp::Epoch(1_700_000_000),
+            expires: LicenseTimestamp::Epoch(1_800_000_000),
+            v: 2,
+        };
+        let payload_bytes = serde_json::to_vec(&payload).unwrap();
+        let payload_sig = intermediate_signer.sign(&payload_bytes);
+
+        let blob = format!(
+            "1.{}.{}.{}.{}",
+            B64.encode(root_sig.to_bytes()),
+            B64.encode(&intermediate_bytes),
+            B64.encode(payload_sig.to_bytes()),
+            B64.encode(&payload_bytes),
         );
+
+        let err = verify_license_blob_from(&blob, store, 1).unwrap_err();
+        assert_eq!(err, "license validity exceeds delegated signing window");
     }
 
-    Ok(())
+    #[test]
+    fn key_id_below_floor_is_refused() {
+        let root = test_root_key();
+        let root_b64 = B64.encode(root.verifying_key().to_bytes());
+        let store: &[(u8, &str)] = &[(1, &root_b64)];
+
+        // Key 1 is present in the store but `min_key_id` of 2 retires it.
+        assert!(root_verifying_key_from(store, 2, 1).is_err());
+        assert!(root_verifying_key_from(store, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn lower_seq_revocation_list_is_ignored() {
+        let cached = RevocationList {
+            revoked_machine_ids: vec!["machine-abc".to_string()],
+            not_valid_after: 9_999_999_999,
+            seq: 5,
+        };
+        let stale = RevocationList {
+            revoked_machine_ids: vec![],
+            not_valid_after: 9_999_999_999,
+            seq: 3,
+        };
+        assert!(!should_accept_revocation_list(&stale, Some(&cached)));
+
+        let fresh = RevocationList {
+            revoked_machine_ids: vec![],
+            not_valid_after: 9_999_999_999,
+            seq: 6,
+        };
+        assert!(should_accept_revocation_list(&fresh, Some(&cached)));
+        assert!(should_accept_revocation_list(&fresh, None));
+    }
 }
@@ -10,6 +10,7 @@
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -20,6 +21,17 @@ const UPDATE_SERVER_URL: &str = "https://api.promptshield.com";
 /// Current app version — read from tauri.conf.json at compile time.
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Default release channel for users who haven't chosen one.
+const DEFAULT_CHANNEL: &str = "stable";
+
+/// Minisign public key used to verify update manifests/packages (set during
+/// build / release; generated with `minisign -G`). TLS and the update server
+/// are not trusted roots — this key is the only thing standing between a
+/// compromised `api.promptshield.com` and an installer that runs on the
+/// user's machine.
+const UPDATE_PUBLIC_KEY_B64: &str =
+    "RWTx5Zr1tiHQLx9yjlNpSPQLQP5AvTLhL0BU0izMakQ2yQbE5WHE8HEr";
+
 // ── Types ────────────────────────────────────────────────────────────────
 
 /// Update manifest returned by the server.
@@ -37,9 +49,70 @@ pub struct UpdateManifest {
     pub sha256: String,
     /// Package file size in bytes
     pub size: u64,
+    /// Minisign signature (base64, `Signature::decode_string` format) over
+    /// the package bytes, signed with the key matching [`UPDATE_PUBLIC_KEY_B64`].
+    pub signature: String,
+    /// Release channel this manifest belongs to, e.g. "stable", "beta", "nightly".
+    #[serde(default = "default_channel")]
+    pub channel: String,
     /// Whether this update is mandatory
     #[serde(default)]
     pub mandatory: bool,
+    /// Windows only: install via a UAC-elevated scheduled task instead of
+    /// spawning the installer directly. See [`launch_installer`].
+    #[serde(default)]
+    pub elevated: bool,
+    /// Per-target overrides keyed by `{os}-{arch}` (e.g. `windows-x86_64`,
+    /// `darwin-aarch64`, `linux-x86_64`), mirroring the per-target map
+    /// Tauri's own updater reads in `RemoteRelease::from_release`. When the
+    /// entry matching [`current_target`] is present, it takes the place of
+    /// the flat `url`/`sha256`/`size`/`signature` fields above, so one signed
+    /// manifest can drive updates for every build. Absent (or missing the
+    /// running target) falls back to those flat fields, for servers that
+    /// already do their own platform routing.
+    #[serde(default)]
+    pub platforms: Option<HashMap<String, PlatformRelease>>,
+}
+
+/// Download info for a single `{os}-{arch}` target within
+/// [`UpdateManifest::platforms`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformRelease {
+    pub url: String,
+    pub sha256: String,
+    pub size: u64,
+    pub signature: String,
+}
+
+impl UpdateManifest {
+    /// Resolve this manifest's download info for the running platform: the
+    /// `platforms` entry matching [`current_target`] if present, otherwise
+    /// the manifest's flat fields.
+    fn resolve_release(&self) -> (&str, &str, u64, &str) {
+        if let Some(entry) = self
+            .platforms
+            .as_ref()
+            .and_then(|platforms| platforms.get(&current_target()))
+        {
+            return (&entry.url, &entry.sha256, entry.size, &entry.signature);
+        }
+        (&self.url, &self.sha256, self.size, &self.signature)
+    }
+}
+
+fn default_channel() -> String {
+    DEFAULT_CHANNEL.to_string()
+}
+
+/// The `{os}-{arch}` target key for the running build, e.g. `darwin-aarch64`
+/// or `windows-x86_64`. Uses `darwin` rather than Rust's `macos` to match the
+/// target keys Tauri's own updater (`RemoteRelease::from_release`) expects.
+fn current_target() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{}-{}", os, std::env::consts::ARCH)
 }
 
 /// Result of checking for updates.
@@ -53,6 +126,8 @@ pub struct UpdateCheckResult {
     pub latest_version: Option<String>,
     /// Full manifest (if update is available)
     pub manifest: Option<UpdateManifest>,
+    /// The release channel this check was performed against.
+    pub channel: String,
     /// Error message if the check failed
     pub error: Option<String>,
 }
@@ -79,9 +154,16 @@ pub struct InstallResult {
 pub struct OfflinePackageMeta {
     pub version: String,
     pub sha256: String,
+    /// Minisign signature (base64) over the installer bytes extracted from
+    /// the package, verified the same way as [`UpdateManifest::signature`].
+    pub signature: String,
     pub notes: String,
     pub pub_date: String,
     pub platform: String,
+    /// Windows only: install via a UAC-elevated scheduled task instead of
+    /// spawning the installer directly. See [`launch_installer`].
+    #[serde(default)]
+    pub elevated: bool,
 }
 
 // ── Helpers ──────────────────────────────────────────────────────────────
@@ -105,27 +187,59 @@ fn updates_dir() -> PathBuf {
     base
 }
 
-/// Compare two semver-like version strings. Returns true if `remote` > `local`.
+/// Path where the user's chosen release channel is persisted.
+fn channel_file_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("promptshield")
+        .join("update-channel")
+}
+
+/// Get the persisted release channel, defaulting to [`DEFAULT_CHANNEL`].
+pub fn get_channel() -> String {
+    fs::read_to_string(channel_file_path())
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_CHANNEL.to_string())
+}
+
+/// Persist the user's chosen release channel, e.g. "stable", "beta", "nightly".
+pub fn set_channel(channel: &str) -> Result<(), String> {
+    let path = channel_file_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Cannot create settings dir: {}", e))?;
+    }
+    std::fs::write(&path, channel).map_err(|e| format!("Cannot write update channel: {}", e))
+}
+
+/// Parse a version string as semver, tolerating a leading `v` and short
+/// versions like `0.2` or `1` by padding them out to `major.minor.patch`
+/// (preserving any pre-release/build suffix).
+fn parse_semver(v: &str) -> Option<semver::Version> {
+    let trimmed = v.trim().trim_start_matches('v');
+    if let Ok(parsed) = semver::Version::parse(trimmed) {
+        return Some(parsed);
+    }
+    let split_at = trimmed.find(['-', '+']).unwrap_or(trimmed.len());
+    let (core, suffix) = trimmed.split_at(split_at);
+    let mut components: Vec<&str> = core.split('.').collect();
+    while components.len() < 3 {
+        components.push("0");
+    }
+    semver::Version::parse(&format!("{}{}", components.join("."), suffix)).ok()
+}
+
+/// Compare two version strings using full semver ordering, including
+/// pre-release and build metadata. Returns true if `remote` > `local`.
+///
+/// A pre-release version (`1.0.0-rc.1`) orders *lower* than its release
+/// (`1.0.0`), per semver's own comparison rules.
 pub fn is_newer_version(local: &str, remote: &str) -> bool {
-    let parse = |v: &str| -> Vec<u64> {
-        v.trim_start_matches('v')
-            .split('.')
-            .filter_map(|s| s.parse::<u64>().ok())
-            .collect()
-    };
-    let l = parse(local);
-    let r = parse(remote);
-    for i in 0..std::cmp::max(l.len(), r.len()) {
-        let lv = l.get(i).copied().unwrap_or(0);
-        let rv = r.get(i).copied().unwrap_or(0);
-        if rv > lv {
-            return true;
-        }
-        if rv < lv {
-            return false;
-        }
+    match (parse_semver(local), parse_semver(remote)) {
+        (Some(l), Some(r)) => r > l,
+        _ => false,
     }
-    false
 }
 
 /// Compute SHA-256 hex digest of a file.
@@ -136,21 +250,57 @@ fn sha256_file(path: &Path) -> Result<String, String> {
     Ok(hex::encode(hasher.finalize()))
 }
 
+/// Verify `package_bytes` against a minisign `signature` using the embedded
+/// [`UPDATE_PUBLIC_KEY_B64`]. The SHA-256 check elsewhere is a fast integrity
+/// pre-filter; this is what actually proves the package came from us — an
+/// attacker who controls the update server or TLS still can't forge this
+/// without the offline signing key.
+fn verify_package_signature(package_bytes: &[u8], signature: &str) -> Result<(), String> {
+    use minisign_verify::{PublicKey, Signature};
+
+    let public_key = PublicKey::from_base64(UPDATE_PUBLIC_KEY_B64)
+        .map_err(|e| format!("Bad embedded update public key: {}", e))?;
+    let signature = Signature::decode_string(signature)
+        .map_err(|e| format!("Bad update signature encoding: {}", e))?;
+    public_key
+        .verify(package_bytes, &signature, false)
+        .map_err(|_| "Update signature verification failed — refusing to install".to_string())
+}
+
 // ── Online update check ──────────────────────────────────────────────────
 
-/// Check the update server for a newer version.
+/// Check the update server for a newer version on the persisted release
+/// channel (see [`get_channel`]/[`set_channel`]).
+///
+/// A beta user is never offered an older stable build and a stable user
+/// never silently jumps to a nightly: the server is expected to scope the
+/// manifest to the requested channel, and a manifest that reports a
+/// different channel than requested is rejected rather than trusted.
 pub async fn check_for_updates() -> UpdateCheckResult {
+    let channel = get_channel();
     let url = format!(
-        "{}/updates/check?version={}&platform={}",
+        "{}/updates/check?version={}&platform={}&channel={}",
         update_server_url(),
         CURRENT_VERSION,
         std::env::consts::OS,
+        channel,
     );
 
     match reqwest::get(&url).await {
         Ok(resp) => {
             if resp.status().is_success() {
                 match resp.json::<UpdateManifest>().await {
+                    Ok(manifest) if manifest.channel != channel => UpdateCheckResult {
+                        update_available: false,
+                        current_version: CURRENT_VERSION.to_string(),
+                        latest_version: None,
+                        manifest: None,
+                        channel: channel.clone(),
+                        error: Some(format!(
+                            "Server returned a {} manifest for the {} channel",
+                            manifest.channel, channel
+                        )),
+                    },
                     Ok(manifest) => {
                         let available = is_newer_version(CURRENT_VERSION, &manifest.version);
                         UpdateCheckResult {
@@ -158,6 +308,7 @@ pub async fn check_for_updates() -> UpdateCheckResult {
                             current_version: CURRENT_VERSION.to_string(),
                             latest_version: Some(manifest.version.clone()),
                             manifest: if available { Some(manifest) } else { None },
+                            channel: channel.clone(),
                             error: None,
                         }
                     }
@@ -166,6 +317,7 @@ pub async fn check_for_updates() -> UpdateCheckResult {
                         current_version: CURRENT_VERSION.to_string(),
                         latest_version: None,
                         manifest: None,
+                        channel: channel.clone(),
                         error: Some(format!("Failed to parse update manifest: {}", e)),
                     },
                 }
@@ -176,6 +328,7 @@ pub async fn check_for_updates() -> UpdateCheckResult {
                     current_version: CURRENT_VERSION.to_string(),
                     latest_version: Some(CURRENT_VERSION.to_string()),
                     manifest: None,
+                    channel: channel.clone(),
                     error: None,
                 }
             } else {
@@ -184,6 +337,7 @@ pub async fn check_for_updates() -> UpdateCheckResult {
                     current_version: CURRENT_VERSION.to_string(),
                     latest_version: None,
                     manifest: None,
+                    channel: channel.clone(),
                     error: Some(format!("Server returned status {}", resp.status())),
                 }
             }
@@ -193,6 +347,7 @@ pub async fn check_for_updates() -> UpdateCheckResult {
             current_version: CURRENT_VERSION.to_string(),
             latest_version: None,
             manifest: None,
+            channel,
             error: Some(format!("Network error: {}", e)),
         },
     }
@@ -206,17 +361,14 @@ pub async fn download_and_install(
     app: &tauri::AppHandle,
 ) -> InstallResult {
     let dir = updates_dir();
+    let (url, sha256, size, signature) = manifest.resolve_release();
 
     // Derive filename from URL
-    let filename = manifest
-        .url
-        .rsplit('/')
-        .next()
-        .unwrap_or("update-package.exe");
+    let filename = url.rsplit('/').next().unwrap_or("update-package.exe");
     let dest = dir.join(filename);
 
     // Download with progress events
-    match download_file(&manifest.url, &dest, &manifest.sha256, manifest.size, app).await {
+    match download_file(url, &dest, sha256, signature, size, app).await {
         Ok(_) => {}
         Err(e) => {
             return InstallResult {
@@ -228,48 +380,110 @@ pub async fn download_and_install(
     }
 
     // Launch the installer
-    launch_installer(&dest)
+    finish_install(&dest, manifest.elevated)
 }
 
-/// Download a file with SHA-256 verification and progress events.
+/// Minimum time between `update-download-progress` emits, so a fast
+/// connection doesn't flood the frontend with one event per chunk.
+const PROGRESS_EMIT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Stream a file to `dest`, verifying its SHA-256 and minisign signature,
+/// emitting throttled progress events as it goes.
+///
+/// If a partial download already exists at `dest`, resumes it with an HTTP
+/// `Range` request and seeds the running hash with the bytes already on
+/// disk. Falls back to a full re-download if the server ignores `Range`.
 async fn download_file(
     url: &str,
     dest: &Path,
     expected_sha256: &str,
+    signature: &str,
     total_size: u64,
     app: &tauri::AppHandle,
 ) -> Result<(), String> {
+    use futures::StreamExt;
     use tauri::Emitter;
 
-    let resp = reqwest::get(url)
-        .await
-        .map_err(|e| format!("Download request failed: {}", e))?;
+    let mut downloaded = dest.metadata().map(|m| m.len()).unwrap_or(0);
+
+    let client = reqwest::Client::new();
+    let mut resp = if downloaded > 0 {
+        client
+            .get(url)
+            .header(reqwest::header::RANGE, format!("bytes={}-", downloaded))
+            .send()
+            .await
+            .map_err(|e| format!("Download request failed: {}", e))?
+    } else {
+        client.get(url).send().await.map_err(|e| format!("Download request failed: {}", e))?
+    };
+
+    let resuming = downloaded > 0 && resp.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+
+    // The server may not honor Range at all — it can ignore it and return a
+    // plain `200`, or reject it outright (e.g. `416 Range Not Satisfiable`
+    // once the local partial file is already complete or past
+    // `Content-Length`). Either way, fall back to a fresh, unranged download
+    // rather than failing the whole update.
+    if downloaded > 0 && !resuming {
+        downloaded = 0;
+        resp = client.get(url).send().await.map_err(|e| format!("Download request failed: {}", e))?;
+    }
 
     if !resp.status().is_success() {
         return Err(format!("Download failed with status {}", resp.status()));
     }
 
-    let total = resp.content_length().unwrap_or(total_size);
-    let bytes = resp
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read download body: {}", e))?;
+    let total = if resuming {
+        resp.content_length().map(|remaining| remaining + downloaded).unwrap_or(total_size)
+    } else {
+        resp.content_length().unwrap_or(total_size)
+    };
+
+    let mut hasher = Sha256::new();
+    let mut file = if resuming {
+        hasher.update(&fs::read(dest).map_err(|e| format!("Failed to read partial file: {}", e))?);
+        fs::OpenOptions::new()
+            .append(true)
+            .open(dest)
+            .map_err(|e| format!("Failed to open file for resume: {}", e))?
+    } else {
+        fs::File::create(dest).map_err(|e| format!("Failed to create file: {}", e))?
+    };
+
+    let mut stream = resp.bytes_stream();
+    let mut last_emit = std::time::Instant::now();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read download chunk: {}", e))?;
+        hasher.update(&chunk);
+        file.write_all(&chunk).map_err(|e| format!("Failed to write chunk: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        if last_emit.elapsed() >= PROGRESS_EMIT_INTERVAL {
+            let _ = app.emit(
+                "update-download-progress",
+                DownloadProgress {
+                    downloaded_bytes: downloaded,
+                    total_bytes: total,
+                    percent: if total > 0 { downloaded as f64 / total as f64 * 100.0 } else { 0.0 },
+                },
+            );
+            last_emit = std::time::Instant::now();
+        }
+    }
 
-    // Emit final progress
     let _ = app.emit(
         "update-download-progress",
         DownloadProgress {
-            downloaded_bytes: bytes.len() as u64,
+            downloaded_bytes: downloaded,
             total_bytes: total,
             percent: 100.0,
         },
     );
 
-    // Verify SHA-256
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
+    // SHA-256 is a fast integrity pre-filter against corruption; it does not
+    // protect against a compromised update server.
     let actual_hash = hex::encode(hasher.finalize());
-
     if actual_hash != expected_sha256.to_lowercase() {
         return Err(format!(
             "SHA-256 mismatch: expected {}, got {}",
@@ -277,17 +491,32 @@ async fn download_file(
         ));
     }
 
-    // Write to disk
-    let mut file =
-        fs::File::create(dest).map_err(|e| format!("Failed to create file: {}", e))?;
-    file.write_all(&bytes)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+    // The signature is what actually proves the package is ours.
+    let bytes = fs::read(dest).map_err(|e| format!("Failed to read downloaded file: {}", e))?;
+    verify_package_signature(&bytes, signature)?;
 
     Ok(())
 }
 
 /// Launch the downloaded installer and signal app restart.
-fn launch_installer(path: &Path) -> InstallResult {
+///
+/// If `elevated` is set (Windows only), the installer is run via a one-shot
+/// UAC-elevated scheduled task instead of being spawned directly, for
+/// installers that need admin rights (e.g. writing to `Program Files`). On
+/// other platforms `elevated` is ignored — there's no scheduled-task
+/// equivalent, and installers there are launched the normal way.
+fn launch_installer(path: &Path, elevated: bool) -> InstallResult {
+    #[cfg(target_os = "windows")]
+    {
+        if elevated {
+            return launch_installer_elevated(path);
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = elevated;
+    }
+
     let ext = path
         .extension()
         .and_then(|e| e.to_str())
@@ -330,6 +559,299 @@ fn launch_installer(path: &Path) -> InstallResult {
     }
 }
 
+/// Run the installer at `path` via a one-shot, `schtasks`-registered
+/// scheduled task running at the `HIGHEST` run level, so Windows prompts for
+/// UAC elevation instead of the installer silently failing to write to a
+/// protected directory. The task is deleted again once it has been started,
+/// whether or not elevation was granted.
+#[cfg(target_os = "windows")]
+fn launch_installer_elevated(path: &Path) -> InstallResult {
+    let task_name = format!("PromptShieldUpdate{}", std::process::id());
+    let command = format!("\"{}\" --update /S", path.to_string_lossy());
+
+    let create = std::process::Command::new("schtasks")
+        .args([
+            "/create", "/tn", &task_name, "/tr", &command, "/sc", "once", "/st", "00:00", "/rl",
+            "HIGHEST", "/f",
+        ])
+        .output();
+
+    let registered = match create {
+        Ok(o) if o.status.success() => true,
+        Ok(o) => {
+            return InstallResult {
+                success: false,
+                message: format!(
+                    "Failed to register elevated install task: {}",
+                    String::from_utf8_lossy(&o.stderr).trim()
+                ),
+                needs_restart: false,
+            }
+        }
+        Err(e) => {
+            return InstallResult {
+                success: false,
+                message: format!("Failed to register elevated install task: {}", e),
+                needs_restart: false,
+            }
+        }
+    };
+
+    let run = std::process::Command::new("schtasks")
+        .args(["/run", "/tn", &task_name])
+        .output();
+
+    let run_result = match run {
+        Ok(o) if o.status.success() => Ok(()),
+        Ok(o) => Err(format!(
+            "Elevated install was not started — UAC elevation may have been refused: {}",
+            String::from_utf8_lossy(&o.stderr).trim()
+        )),
+        Err(e) => Err(format!("Failed to start elevated install task: {}", e)),
+    };
+
+    if registered {
+        let _ = std::process::Command::new("schtasks")
+            .args(["/delete", "/tn", &task_name, "/f"])
+            .output();
+    }
+
+    match run_result {
+        Ok(()) => InstallResult {
+            success: true,
+            message: "Elevated update installer launched. The app will restart.".to_string(),
+            needs_restart: true,
+        },
+        Err(message) => InstallResult { success: false, message, needs_restart: false },
+    }
+}
+
+// ── Multi-format archive extraction + in-place replacement ───────────────
+//
+// Windows ships updates as `.exe`/`.msi` installers that `launch_installer`
+// runs directly. macOS/Linux instead ship a compressed archive that must be
+// unpacked and swapped in for the running executable.
+
+/// Recognized archive formats for self-update packages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    TarGz,
+    TarBz2,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from the file extension, falling back to magic-byte
+    /// sniffing for extension-less packages.
+    fn detect(path: &Path) -> Result<Self, String> {
+        let name = path.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            return Ok(ArchiveFormat::TarGz);
+        }
+        if name.ends_with(".tar.bz2") || name.ends_with(".tbz2") {
+            return Ok(ArchiveFormat::TarBz2);
+        }
+        if name.ends_with(".zip") {
+            return Ok(ArchiveFormat::Zip);
+        }
+
+        let mut file = fs::File::open(path).map_err(|e| format!("Cannot open archive: {}", e))?;
+        let mut header = [0u8; 4];
+        let n = std::io::Read::read(&mut file, &mut header).unwrap_or(0);
+        match &header[..n] {
+            [0x1f, 0x8b, ..] => Ok(ArchiveFormat::TarGz),
+            [0x42, 0x5a, 0x68, ..] => Ok(ArchiveFormat::TarBz2),
+            [0x50, 0x4b, 0x03, 0x04] | [0x50, 0x4b, 0x05, 0x06] => Ok(ArchiveFormat::Zip),
+            _ => Err("Unrecognized archive format".to_string()),
+        }
+    }
+}
+
+/// Extract `archive_path` into a fresh staging directory under
+/// `updates_dir()` and return that directory.
+fn extract_archive(archive_path: &Path) -> Result<PathBuf, String> {
+    let format = ArchiveFormat::detect(archive_path)?;
+    let staging = updates_dir().join("staging");
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|e| format!("Cannot clear staging dir: {}", e))?;
+    }
+    fs::create_dir_all(&staging).map_err(|e| format!("Cannot create staging dir: {}", e))?;
+
+    match format {
+        ArchiveFormat::Zip => {
+            let file =
+                fs::File::open(archive_path).map_err(|e| format!("Cannot open archive: {}", e))?;
+            let mut archive =
+                zip::ZipArchive::new(file).map_err(|e| format!("Invalid zip archive: {}", e))?;
+            archive
+                .extract(&staging)
+                .map_err(|e| format!("Failed to extract zip archive: {}", e))?;
+        }
+        ArchiveFormat::TarGz => {
+            let file =
+                fs::File::open(archive_path).map_err(|e| format!("Cannot open archive: {}", e))?;
+            tar::Archive::new(flate2::read::GzDecoder::new(file))
+                .unpack(&staging)
+                .map_err(|e| format!("Failed to extract tar.gz archive: {}", e))?;
+        }
+        ArchiveFormat::TarBz2 => {
+            let file =
+                fs::File::open(archive_path).map_err(|e| format!("Cannot open archive: {}", e))?;
+            tar::Archive::new(bzip2::read::BzDecoder::new(file))
+                .unpack(&staging)
+                .map_err(|e| format!("Failed to extract tar.bz2 archive: {}", e))?;
+        }
+    }
+
+    Ok(staging)
+}
+
+/// Find the extracted executable in a staging directory: the largest
+/// regular file that isn't `manifest.json`, searched recursively.
+///
+/// Real release archives (`tar czf app-1.2.3-linux-x86_64.tar.gz
+/// app-1.2.3-linux-x86_64/`) commonly extract into a single top-level
+/// directory rather than dropping the binary right at the archive root, so a
+/// single-level scan isn't enough.
+fn find_extracted_binary(staging: &Path) -> Result<PathBuf, String> {
+    let mut best: Option<(PathBuf, u64)> = None;
+    collect_largest_file(staging, &mut best)?;
+    best.map(|(path, _)| path)
+        .ok_or_else(|| "No executable found in update archive".to_string())
+}
+
+/// Recursively walk `dir`, updating `best` with the largest regular file
+/// found that isn't named `manifest.json`.
+fn collect_largest_file(dir: &Path, best: &mut Option<(PathBuf, u64)>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Cannot read staging dir: {}", e))? {
+        let entry = entry.map_err(|e| format!("Cannot read staging entry: {}", e))?;
+        let path = entry.path();
+        if path.file_name().and_then(|n| n.to_str()) == Some("manifest.json") {
+            continue;
+        }
+        if path.is_dir() {
+            collect_largest_file(&path, best)?;
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if best.as_ref().map(|(_, best_size)| size > *best_size).unwrap_or(true) {
+            *best = Some((path, size));
+        }
+    }
+    Ok(())
+}
+
+/// Atomically replace the running executable with `new_binary`.
+///
+/// Moves the current executable aside to a `.old` sidecar (removed on the
+/// next launch by [`cleanup_old_binary`]) and moves the new one into place,
+/// preserving the executable permission bit on Unix. Rolls the move back if
+/// putting the new binary in place fails, so a partial failure never leaves
+/// the app without an executable.
+#[cfg(not(target_os = "windows"))]
+fn replace_running_executable(new_binary: &Path) -> Result<(), String> {
+    let current =
+        std::env::current_exe().map_err(|e| format!("Cannot locate running executable: {}", e))?;
+    let old = current.with_extension("old");
+
+    let _ = fs::remove_file(&old);
+    fs::rename(&current, &old)
+        .map_err(|e| format!("Cannot move current executable aside: {}", e))?;
+
+    if let Err(e) = stage_and_swap_in(new_binary, &current) {
+        let _ = fs::rename(&old, &current); // roll back
+        return Err(e);
+    }
+
+    use std::os::unix::fs::PermissionsExt;
+    if let Ok(meta) = fs::metadata(&current) {
+        let mut perms = meta.permissions();
+        perms.set_mode(perms.mode() | 0o111);
+        let _ = fs::set_permissions(&current, perms);
+    }
+
+    Ok(())
+}
+
+/// Move `new_binary` into place at `current`.
+///
+/// `new_binary` lives under the updates staging directory, which is
+/// typically a different filesystem/mount than the install location (e.g.
+/// `/opt/promptshield` vs. the user's data dir), so a direct `fs::rename`
+/// fails with `EXDEV`. Copy it alongside `current` first and rename from
+/// there instead, which keeps the swap atomic since both paths now share a
+/// filesystem. If even that rename fails, fall back to copying straight
+/// over `current`, preserving permissions but giving up atomicity.
+fn stage_and_swap_in(new_binary: &Path, current: &Path) -> Result<(), String> {
+    let staged = current.with_extension("new");
+    let _ = fs::remove_file(&staged);
+
+    fs::copy(new_binary, &staged).map_err(|e| format!("Cannot stage new executable: {}", e))?;
+
+    if let Err(rename_err) = fs::rename(&staged, current) {
+        let _ = fs::remove_file(&staged);
+        fs::copy(new_binary, current).map_err(|_| {
+            format!("Cannot move new executable into place: {}", rename_err)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Remove a `.old` sidecar left behind by a previous update swap. Call once on startup.
+pub fn cleanup_old_binary() {
+    if let Ok(current) = std::env::current_exe() {
+        let _ = fs::remove_file(current.with_extension("old"));
+    }
+}
+
+/// Extract a self-update archive and atomically swap it in for the running
+/// executable. Unix-only — Windows updates go through [`launch_installer`].
+#[cfg(not(target_os = "windows"))]
+fn install_archive_update(archive_path: &Path) -> InstallResult {
+    let staging = match extract_archive(archive_path) {
+        Ok(dir) => dir,
+        Err(e) => {
+            return InstallResult {
+                success: false,
+                message: format!("Extraction failed: {}", e),
+                needs_restart: false,
+            }
+        }
+    };
+
+    let binary = match find_extracted_binary(&staging) {
+        Ok(p) => p,
+        Err(e) => return InstallResult { success: false, message: e, needs_restart: false },
+    };
+
+    match replace_running_executable(&binary) {
+        Ok(()) => InstallResult {
+            success: true,
+            message: "Update installed. The app will restart.".to_string(),
+            needs_restart: true,
+        },
+        Err(e) => InstallResult { success: false, message: e, needs_restart: false },
+    }
+}
+
+/// Finish installing a downloaded/extracted update package.
+///
+/// On Windows this always launches the bundled installer, honoring `elevated`
+/// (see [`launch_installer`]). On macOS/Linux, an archive package is
+/// extracted and swapped in for the running executable in place — `elevated`
+/// doesn't apply there; a non-archive package (e.g. a signed `.pkg`) falls
+/// back to `launch_installer` like Windows does.
+fn finish_install(path: &Path, elevated: bool) -> InstallResult {
+    #[cfg(not(target_os = "windows"))]
+    {
+        if ArchiveFormat::detect(path).is_ok() {
+            return install_archive_update(path);
+        }
+    }
+    launch_installer(path, elevated)
+}
+
 // ── Offline update ───────────────────────────────────────────────────────
 
 /// Read and validate an offline update package.
@@ -362,6 +884,17 @@ pub fn read_offline_package(path: &str) -> Result<OfflinePackageMeta, String> {
     let meta: OfflinePackageMeta = serde_json::from_str(&manifest_str)
         .map_err(|e| format!("Invalid manifest.json: {}", e))?;
 
+    // Reject a package built for a different OS before anything else — a
+    // macOS package handed to a Windows machine should fail immediately
+    // rather than downloading/extracting an installer that can't run there.
+    if meta.platform != std::env::consts::OS {
+        return Err(format!(
+            "Package is built for {}, but this machine is running {}",
+            meta.platform,
+            std::env::consts::OS
+        ));
+    }
+
     // Verify it's newer than current
     if !is_newer_version(CURRENT_VERSION, &meta.version) {
         return Err(format!(
@@ -432,6 +965,19 @@ pub fn install_offline_package(path: &str) -> InstallResult {
         }
     };
 
+    // Reject a package built for a different OS before extracting anything.
+    if meta.platform != std::env::consts::OS {
+        return InstallResult {
+            success: false,
+            message: format!(
+                "Package is built for {}, but this machine is running {}",
+                meta.platform,
+                std::env::consts::OS
+            ),
+            needs_restart: false,
+        };
+    }
+
     // Find installer file (any file that isn't manifest.json)
     let dest_dir = updates_dir();
     let mut installer_path: Option<PathBuf> = None;
@@ -478,7 +1024,7 @@ pub fn install_offline_package(path: &str) -> InstallResult {
         }
     };
 
-    // Verify SHA-256 of the extracted installer
+    // Verify SHA-256 of the extracted installer (fast pre-filter)
     match sha256_file(&installer) {
         Ok(hash) => {
             if hash != meta.sha256.to_lowercase() {
@@ -501,8 +1047,29 @@ pub fn install_offline_package(path: &str) -> InstallResult {
         }
     }
 
-    // Launch installer
-    launch_installer(&installer)
+    // Verify the minisign signature — what actually proves this package came
+    // from us, since a `.promptshield-update` file could otherwise be handed
+    // to a user by anyone.
+    let installer_bytes = match fs::read(&installer) {
+        Ok(b) => b,
+        Err(e) => {
+            return InstallResult {
+                success: false,
+                message: format!("Failed to read extracted installer: {}", e),
+                needs_restart: false,
+            }
+        }
+    };
+    if let Err(e) = verify_package_signature(&installer_bytes, &meta.signature) {
+        return InstallResult {
+            success: false,
+            message: e,
+            needs_restart: false,
+        };
+    }
+
+    // Launch installer / extract & swap in
+    finish_install(&installer, meta.elevated)
 }
 
 /// Clean up any downloaded update files.
@@ -527,4 +1094,114 @@ mod tests {
         assert!(!is_newer_version("0.1.0", "0.1.0"));
         assert!(is_newer_version("v0.1.0", "v0.2.0"));
     }
+
+    #[test]
+    fn test_prerelease_ordering() {
+        assert!(is_newer_version("1.0.0-rc.1", "1.0.0"));
+        assert!(!is_newer_version("1.0.0", "1.0.0-rc.1"));
+        assert!(is_newer_version("1.0.0-alpha", "1.0.0-beta"));
+        assert!(!is_newer_version("0.2.0", "0.2.0-beta.1"));
+    }
+
+    #[test]
+    fn test_mixed_length_versions() {
+        assert!(is_newer_version("0.2", "0.2.1"));
+        assert!(!is_newer_version("0.2", "0.2.0"));
+        assert!(is_newer_version("1", "1.0.1"));
+        assert!(!is_newer_version("1", "1.0.0"));
+    }
+
+    #[test]
+    fn verify_package_signature_rejects_malformed_signature() {
+        let err = verify_package_signature(b"package bytes", "not a minisign signature").unwrap_err();
+        assert!(err.contains("Bad update signature encoding"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn verify_package_signature_rejects_empty_signature() {
+        let err = verify_package_signature(b"package bytes", "").unwrap_err();
+        assert!(err.contains("Bad update signature encoding"), "unexpected error: {}", err);
+    }
+
+    /// Fresh scratch directory for a single test, cleaned up from any
+    /// previous run of the same test (there's no guaranteed teardown).
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "promptshield-updater-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn archive_format_detect_uses_extension() {
+        assert_eq!(
+            ArchiveFormat::detect(Path::new("app-1.2.3-linux-x86_64.tar.gz")).unwrap(),
+            ArchiveFormat::TarGz
+        );
+        assert_eq!(
+            ArchiveFormat::detect(Path::new("app-1.2.3.tgz")).unwrap(),
+            ArchiveFormat::TarGz
+        );
+        assert_eq!(
+            ArchiveFormat::detect(Path::new("app-1.2.3.tar.bz2")).unwrap(),
+            ArchiveFormat::TarBz2
+        );
+        assert_eq!(
+            ArchiveFormat::detect(Path::new("app-1.2.3-windows.zip")).unwrap(),
+            ArchiveFormat::Zip
+        );
+    }
+
+    #[test]
+    fn archive_format_detect_falls_back_to_magic_bytes() {
+        let dir = scratch_dir("magic-bytes");
+
+        let gz_path = dir.join("update-package");
+        fs::write(&gz_path, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+        assert_eq!(ArchiveFormat::detect(&gz_path).unwrap(), ArchiveFormat::TarGz);
+
+        let bz2_path = dir.join("update-package-2");
+        fs::write(&bz2_path, *b"BZh9").unwrap();
+        assert_eq!(ArchiveFormat::detect(&bz2_path).unwrap(), ArchiveFormat::TarBz2);
+
+        let zip_path = dir.join("update-package-3");
+        fs::write(&zip_path, [0x50, 0x4b, 0x03, 0x04]).unwrap();
+        assert_eq!(ArchiveFormat::detect(&zip_path).unwrap(), ArchiveFormat::Zip);
+
+        let junk_path = dir.join("update-package-4");
+        fs::write(&junk_path, [0x00, 0x01, 0x02, 0x03]).unwrap();
+        assert!(ArchiveFormat::detect(&junk_path).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn find_extracted_binary_picks_largest_file_and_skips_manifest() {
+        let dir = scratch_dir("find-binary");
+        let nested = dir.join("app-1.2.3-linux-x86_64");
+        fs::create_dir_all(&nested).unwrap();
+
+        fs::write(dir.join("manifest.json"), vec![b'm'; 10_000]).unwrap();
+        fs::write(nested.join("README.txt"), b"hello").unwrap();
+        fs::write(nested.join("app-1.2.3-linux-x86_64"), vec![b'x'; 1_000]).unwrap();
+
+        let found = find_extracted_binary(&dir).unwrap();
+        assert_eq!(found, nested.join("app-1.2.3-linux-x86_64"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn find_extracted_binary_errors_when_only_manifest_present() {
+        let dir = scratch_dir("find-binary-empty");
+        fs::write(dir.join("manifest.json"), b"{}").unwrap();
+
+        assert!(find_extracted_binary(&dir).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }